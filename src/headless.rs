@@ -0,0 +1,53 @@
+//! Headless JSON turn protocol for driving the engine without the egui UI,
+//! so external bots can be benchmarked against each other in batch runs.
+
+use std::io::{self, Read, Write};
+
+use crate::{BattleshipGame, Cell, Ship, Shot};
+
+/// One turn of game state as seen from whichever side is about to fire:
+/// board dimensions, the shots fired so far, and the ship/sunk info needed
+/// to tell hunt mode from target mode.
+#[derive(Debug, serde::Deserialize)]
+struct TurnRequest {
+    rows: usize,
+    cols: usize,
+    shots: Vec<Vec<Shot>>,
+    ships: Vec<Ship>,
+}
+
+/// The move the engine would make for a given `TurnRequest`.
+#[derive(Debug, serde::Serialize)]
+struct TurnResponse {
+    row: usize,
+    col: usize,
+}
+
+/// Reads one `TurnRequest` as JSON from stdin and writes the chosen move
+/// back to stdout as a `TurnResponse`. Meant to be run once per turn by an
+/// external harness, so strategies (e.g. random vs. probability-density)
+/// can be pitted against each other without opening a window.
+pub fn run() -> io::Result<()> {
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input)?;
+    let request: TurnRequest = serde_json::from_str(&input)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+    let game = BattleshipGame {
+        board: vec![vec![Cell::Empty; request.cols]; request.rows],
+        shots: request.shots,
+        ships: request.ships,
+        ships_config: vec![],
+        game_over: false,
+        message: String::new(),
+        play_again: false,
+        rows: request.rows,
+        cols: request.cols,
+        turns: 0,
+    };
+    let (row, col) = game.ai_best_shot();
+
+    let json = serde_json::to_string(&TurnResponse { row, col })?;
+    io::stdout().write_all(json.as_bytes())?;
+    io::stdout().write_all(b"\n")
+}
@@ -1,3 +1,6 @@
+mod headless;
+mod net;
+
 #[derive(Debug, serde::Serialize)]
 struct GameStats {
     turns: usize,
@@ -14,32 +17,140 @@ use rand::seq::SliceRandom;
 use rand::Rng;
 
 const SHIPS: [(&str, usize); 3] = [("Destroyer", 2), ("Cruiser", 3), ("Battleship", 4)];
+const SAVE_FILE: &str = "battleship_save.json";
+const FLEET_CONFIG_FILE: &str = "fleet.toml";
+
+/// One ship entry in a `Fleet` config: a name, a hull length, and how many
+/// copies of it the fleet carries.
+#[derive(Debug, Clone, serde::Deserialize)]
+struct ShipConfig {
+    name: String,
+    length: usize,
+    #[serde(default = "default_ship_count")]
+    count: usize,
+}
+
+fn default_ship_count() -> usize {
+    1
+}
+
+/// Board dimensions and ship roster for a match, loaded from a TOML file so
+/// players can define custom fleets without recompiling.
+#[derive(Debug, Clone, serde::Deserialize)]
+struct Fleet {
+    rows: usize,
+    cols: usize,
+    ships: Vec<ShipConfig>,
+}
 
-#[derive(Clone, Copy, PartialEq)]
+impl Fleet {
+    /// The original hardcoded three-ship fleet, used when no config file is
+    /// present.
+    fn classic(rows: usize, cols: usize) -> Self {
+        Fleet {
+            rows,
+            cols,
+            ships: SHIPS
+                .iter()
+                .map(|&(name, length)| ShipConfig { name: name.to_owned(), length, count: 1 })
+                .collect(),
+        }
+    }
+
+    /// Loads a fleet definition from a TOML file, validating that the whole
+    /// roster actually fits on the configured board.
+    fn load_from(path: impl AsRef<std::path::Path>) -> std::io::Result<Fleet> {
+        let text = std::fs::read_to_string(path)?;
+        let fleet: Fleet = toml::from_str(&text)
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+        fleet.validate()?;
+        Ok(fleet)
+    }
+
+    fn validate(&self) -> std::io::Result<()> {
+        let longest_side = self.rows.max(self.cols);
+        if let Some(ship) = self.ships.iter().find(|s| s.length > longest_side) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!(
+                    "the {} is {} cells long, which doesn't fit on a {}x{} board",
+                    ship.name, ship.length, self.rows, self.cols
+                ),
+            ));
+        }
+
+        let required_cells: usize = self.ships.iter().map(|s| s.length * s.count).sum();
+        if required_cells > self.rows * self.cols {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!(
+                    "fleet needs {required_cells} cells but the {}x{} board only has {}",
+                    self.rows,
+                    self.cols,
+                    self.rows * self.cols
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Expands ship counts into one `(name, length)` entry per hull, in the
+    /// order `start_game` and `try_place_ship` index into.
+    fn expand(&self) -> Vec<(String, usize)> {
+        self.ships
+            .iter()
+            .flat_map(|s| std::iter::repeat((s.name.clone(), s.length)).take(s.count))
+            .collect()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
 enum Cell {
     Empty,
     Ship(usize), // ship index
 }
 
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
 enum Shot {
     Untargeted,
     Miss,
     Hit,
 }
 
-#[derive(Clone)]
+/// Why a requested ship placement was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PlacementError {
+    /// The ship would extend past the edge of the board.
+    OutOfBounds,
+    /// The ship would overlap a cell already occupied by another ship.
+    Overlaps,
+}
+
+impl std::fmt::Display for PlacementError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlacementError::OutOfBounds => write!(f, "that placement goes off the board"),
+            PlacementError::Overlaps => write!(f, "that placement overlaps another ship"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 struct Ship {
-    name: &'static str,
+    name: String,
     length: usize,
     positions: Vec<(usize, usize)>,
     sunk: bool,
 }
 
+#[derive(serde::Serialize, serde::Deserialize)]
 struct BattleshipGame {
     board: Vec<Vec<Cell>>,
     shots: Vec<Vec<Shot>>,
     ships: Vec<Ship>,
+    /// The roster this match was set up with, expanded to one `(name,
+    /// length)` entry per hull; `try_place_ship` indexes into it.
+    ships_config: Vec<(String, usize)>,
     game_over: bool,
     message: String,
     play_again: bool,
@@ -49,67 +160,130 @@ struct BattleshipGame {
 }
 
 impl BattleshipGame {
-    fn new(rows: usize, cols: usize) -> Self {
+    fn new(fleet: &Fleet) -> Self {
         let mut game = BattleshipGame {
-            board: vec![vec![Cell::Empty; cols]; rows],
-            shots: vec![vec![Shot::Untargeted; cols]; rows],
+            board: vec![vec![Cell::Empty; fleet.cols]; fleet.rows],
+            shots: vec![vec![Shot::Untargeted; fleet.cols]; fleet.rows],
             ships: vec![],
+            ships_config: fleet.expand(),
             game_over: false,
             message: "Welcome to Battleship!".to_owned(),
             play_again: false,
-            rows,
-            cols,
+            rows: fleet.rows,
+            cols: fleet.cols,
             turns: 0,
         };
-        game.start_game(rows, cols);
+        if let Err(err) = game.start_game() {
+            game.message = err;
+        }
         game
     }
 
-    fn start_game(&mut self, rows: usize, cols: usize) {
+    /// Creates a fresh, empty board with no ships placed yet, ready for a
+    /// manual placement phase via [`Self::try_place_ship`].
+    fn new_unplaced(fleet: &Fleet) -> Self {
+        BattleshipGame {
+            board: vec![vec![Cell::Empty; fleet.cols]; fleet.rows],
+            shots: vec![vec![Shot::Untargeted; fleet.cols]; fleet.rows],
+            ships: vec![],
+            ships_config: fleet.expand(),
+            game_over: false,
+            message: "Place your fleet to begin.".to_owned(),
+            play_again: false,
+            rows: fleet.rows,
+            cols: fleet.cols,
+            turns: 0,
+        }
+    }
+
+    /// Clears the board and randomly places every ship in `ships_config`,
+    /// going through the same [`Self::try_place_ship`] validation as manual
+    /// placement.
+    ///
+    /// A fleet that passes [`Fleet::validate`] can still be unplaceable in
+    /// practice (e.g. two ships that each span a whole side of a square
+    /// board can never avoid crossing each other), so placement is bounded:
+    /// each ship gets a capped number of random tries, and the whole layout
+    /// is restarted from scratch up to `MAX_LAYOUT_ATTEMPTS` times before
+    /// giving up, rather than looping forever.
+    fn start_game(&mut self) -> Result<(), String> {
+        const MAX_PLACEMENT_ATTEMPTS_PER_SHIP: usize = 200;
+        const MAX_LAYOUT_ATTEMPTS: usize = 50;
+
         self.turns = 0;
-        self.rows = rows;
-        self.cols = cols;
-        self.board = vec![vec![Cell::Empty; cols]; rows];
-        self.shots = vec![vec![Shot::Untargeted; cols]; rows];
-        self.ships.clear();
         self.game_over = false;
         self.message = "Game started!".to_owned();
         self.play_again = false;
         let mut rng = rand::thread_rng();
-        for (ship_idx, (name, length)) in SHIPS.iter().enumerate() {
-            'place: loop {
-                let dir = *[true, false].choose(&mut rng).unwrap(); // true: horizontal, false: vertical
-                let (row, col) = (
-                    rng.gen_range(0..rows),
-                    rng.gen_range(0..cols),
-                );
-                let mut positions = vec![];
-                for i in 0..*length {
-                    let (r, c) = if dir {
-                        (row, col + i)
-                    } else {
-                        (row + i, col)
-                    };
-                    if r >= rows || c >= cols {
-                        continue 'place;
-                    }
-                    if self.board[r][c] != Cell::Empty {
-                        continue 'place;
+
+        for _ in 0..MAX_LAYOUT_ATTEMPTS {
+            self.board = vec![vec![Cell::Empty; self.cols]; self.rows];
+            self.shots = vec![vec![Shot::Untargeted; self.cols]; self.rows];
+            self.ships.clear();
+
+            let mut layout_ok = true;
+            for ship_idx in 0..self.ships_config.len() {
+                let mut placed = false;
+                for _ in 0..MAX_PLACEMENT_ATTEMPTS_PER_SHIP {
+                    let horizontal = *[true, false].choose(&mut rng).unwrap();
+                    let row = rng.gen_range(0..self.rows);
+                    let col = rng.gen_range(0..self.cols);
+                    if self.try_place_ship(ship_idx, row, col, horizontal).is_ok() {
+                        placed = true;
+                        break;
                     }
-                    positions.push((r, c));
                 }
-                for &(r, c) in &positions {
-                    self.board[r][c] = Cell::Ship(ship_idx);
+                if !placed {
+                    layout_ok = false;
+                    break;
                 }
-                self.ships.push(Ship {
-                    name,
-                    length: *length,
-                    positions,
-                    sunk: false,
-                });
-                break;
+            }
+
+            if layout_ok {
+                return Ok(());
             }
         }
+
+        Err("couldn't find a layout for this fleet; try a larger board or fewer/shorter ships"
+            .to_owned())
+    }
+
+    /// Places the next ship from `ships_config` at `(row, col)`, running the
+    /// same overlap/bounds validation used by the random layout in
+    /// [`Self::start_game`]. Manual placement and random placement both go
+    /// through this one path, so neither can produce an invalid board.
+    ///
+    /// `ship_idx` must equal `self.ships.len()`, i.e. ships are placed in
+    /// `ships_config` order, one at a time.
+    fn try_place_ship(
+        &mut self,
+        ship_idx: usize,
+        row: usize,
+        col: usize,
+        horizontal: bool,
+    ) -> Result<(), PlacementError> {
+        let (name, length) = self.ships_config[ship_idx].clone();
+        let mut positions = Vec::with_capacity(length);
+        for i in 0..length {
+            let (r, c) = if horizontal { (row, col + i) } else { (row + i, col) };
+            if r >= self.rows || c >= self.cols {
+                return Err(PlacementError::OutOfBounds);
+            }
+            if self.board[r][c] != Cell::Empty {
+                return Err(PlacementError::Overlaps);
+            }
+            positions.push((r, c));
+        }
+        for &(r, c) in &positions {
+            self.board[r][c] = Cell::Ship(ship_idx);
+        }
+        self.ships.push(Ship {
+            name,
+            length,
+            positions,
+            sunk: false,
+        });
+        Ok(())
     }
 
     fn shoot(&mut self, row: usize, col: usize) {
@@ -141,6 +315,65 @@ impl BattleshipGame {
         }
     }
 
+    /// Picks the next shot using a probability-density heat map over all
+    /// still-valid placements of each unsunk ship.
+    ///
+    /// Cells covered by more possible ship placements accumulate more heat.
+    /// If there are unresolved hits (hits that don't yet belong to a sunk
+    /// ship), the search switches to "target" mode and only counts
+    /// placements that cover at least one of those hits, concentrating fire
+    /// on wounded ships instead of spreading out across the whole board.
+    fn ai_best_shot(&self) -> (usize, usize) {
+        let unresolved_hits: Vec<(usize, usize)> = (0..self.rows)
+            .flat_map(|r| (0..self.cols).map(move |c| (r, c)))
+            .filter(|&(r, c)| {
+                self.shots[r][c] == Shot::Hit
+                    && !self
+                        .ships
+                        .iter()
+                        .any(|ship| ship.sunk && ship.positions.contains(&(r, c)))
+            })
+            .collect();
+        let targeting = !unresolved_hits.is_empty();
+
+        let mut heat = vec![vec![0usize; self.cols]; self.rows];
+        for ship in self.ships.iter().filter(|ship| !ship.sunk) {
+            for row in 0..self.rows {
+                for col in 0..self.cols {
+                    for horizontal in [true, false] {
+                        let mut positions = Vec::with_capacity(ship.length);
+                        let mut valid = true;
+                        for i in 0..ship.length {
+                            let (r, c) = if horizontal { (row, col + i) } else { (row + i, col) };
+                            if r >= self.rows || c >= self.cols || self.shots[r][c] == Shot::Miss {
+                                valid = false;
+                                break;
+                            }
+                            positions.push((r, c));
+                        }
+                        if !valid {
+                            continue;
+                        }
+                        if targeting && !positions.iter().any(|pos| unresolved_hits.contains(pos)) {
+                            continue;
+                        }
+                        for &(r, c) in &positions {
+                            if self.shots[r][c] == Shot::Untargeted {
+                                heat[r][c] += 1;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        (0..self.rows)
+            .flat_map(|r| (0..self.cols).map(move |c| (r, c)))
+            .filter(|&(r, c)| self.shots[r][c] == Shot::Untargeted)
+            .max_by_key(|&(r, c)| heat[r][c])
+            .unwrap_or((0, 0))
+    }
+
     fn game_stats(&self) -> GameStats {
         let mut hits = 0;
         let mut misses = 0;
@@ -161,34 +394,237 @@ impl BattleshipGame {
             total_ships: self.ships.len(),
         }
     }
+
+    /// Writes the full game state to `path` as JSON so a match in progress
+    /// can be restored exactly, including hits and sunk ships.
+    fn save_to(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, json)
+    }
+
+    /// Reads a game state previously written by `save_to`.
+    fn load_from(path: impl AsRef<std::path::Path>) -> std::io::Result<BattleshipGame> {
+        let json = std::fs::read_to_string(path)?;
+        serde_json::from_str(&json)
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+    }
+}
+
+/// Which phase of a local match the UI is currently in.
+enum AppMode {
+    /// Clicking ships from the active fleet onto the board, one at a time,
+    /// before firing begins. `next_ship` indexes into `game.ships_config`.
+    Placing { next_ship: usize, horizontal: bool },
+    /// The fleet is placed and the player is taking shots.
+    Playing,
 }
 
 struct BattleshipApp {
     game: BattleshipGame,
+    mode: AppMode,
+    /// The board size and ship roster new matches are created with; loaded
+    /// from `fleet.toml` at startup or the classic three-ship layout.
+    fleet: Fleet,
     selected_row: usize,
     selected_col: usize,
     input_rows: usize,
     input_cols: usize,
     awaiting_new_game: bool,
+    /// What we've learned about the opponent's board by firing at it; only
+    /// populated once a networked match is underway.
+    opponent_view: Vec<Vec<Shot>>,
+    net: Option<net::NetSession>,
+    net_runtime: Option<tokio::runtime::Runtime>,
+    net_addr: String,
+    is_my_turn: bool,
+    /// Whether `poll_network` has already announced this session's
+    /// handshake completing, so it only happens once per connection.
+    net_handshake_announced: bool,
 }
 
 impl Default for BattleshipApp {
     fn default() -> Self {
-        let default_rows = 8;
-        let default_cols = 8;
+        let fleet = Fleet::load_from(FLEET_CONFIG_FILE).unwrap_or_else(|_| Fleet::classic(8, 8));
         Self {
-            game: BattleshipGame::new(default_rows, default_cols),
+            game: BattleshipGame::new_unplaced(&fleet),
+            mode: AppMode::Placing { next_ship: 0, horizontal: true },
+            input_rows: fleet.rows,
+            input_cols: fleet.cols,
+            opponent_view: vec![vec![Shot::Untargeted; fleet.cols]; fleet.rows],
+            fleet,
             selected_row: 0,
             selected_col: 0,
-            input_rows: default_rows,
-            input_cols: default_cols,
             awaiting_new_game: false,
+            net: None,
+            net_runtime: None,
+            net_addr: "127.0.0.1:9000".to_owned(),
+            is_my_turn: true,
+            net_handshake_announced: false,
+        }
+    }
+}
+
+impl BattleshipApp {
+    /// Kicks off a host/join handshake in the background and returns right
+    /// away; the UI thread never blocks waiting for a peer. `poll_network`
+    /// picks up the result once the connection is ready (or has failed).
+    fn start_network_match(&mut self, role: net::Role) {
+        let runtime = tokio::runtime::Runtime::new().expect("failed to start network runtime");
+        let session = match role {
+            net::Role::Host => net::NetSession::host(&self.net_addr, &runtime.handle().clone()),
+            net::Role::Join => net::NetSession::join(&self.net_addr, &runtime.handle().clone()),
+        };
+        self.net = Some(session);
+        self.net_runtime = Some(runtime);
+        self.net_handshake_announced = false;
+        self.game.message = match role {
+            net::Role::Host => "Waiting for an opponent to connect...".to_owned(),
+            net::Role::Join => "Connecting to host...".to_owned(),
+        };
+    }
+
+    /// Polls the active network session for incoming strikes against our
+    /// own fleet and results for strikes we made against the opponent's.
+    fn poll_network(&mut self) {
+        let Some(net) = self.net.as_ref() else {
+            return;
+        };
+        if let Some(err) = net.take_connect_error() {
+            self.net = None;
+            self.net_runtime = None;
+            self.game.message = format!("Failed to connect: {err}");
+            return;
+        }
+        if !net.is_connected() {
+            return;
+        }
+        let role = net.role;
+
+        if !self.net_handshake_announced {
+            self.net_handshake_announced = true;
+            self.is_my_turn = role == net::Role::Host;
+            self.opponent_view = vec![vec![Shot::Untargeted; self.game.cols]; self.game.rows];
+            self.game.message = "Connected! Waiting for the match to begin.".to_owned();
+        }
+
+        let net = self.net.as_mut().unwrap();
+
+        if let Some(net::ClientMessage::Strike { row, col }) = net.try_recv_strike() {
+            self.game.shoot(row, col);
+            let sunk = self
+                .game
+                .ships
+                .iter()
+                .find(|ship| ship.sunk && ship.positions.contains(&(row, col)))
+                .map(|ship| ship.name.to_owned());
+            let result = self.game.shots[row][col];
+            net.send_result(net::ServerMessage::StrikeResult {
+                pos: (row, col),
+                result,
+                sunk,
+            });
+            if self.game.game_over {
+                // Our own fleet is wiped out: we lost, so the opponent won.
+                self.game.message = "Your fleet has been wiped out — you lose!".to_owned();
+                net.send_result(net::ServerMessage::WonGame {
+                    board: self.game.board.clone(),
+                });
+            }
+            self.is_my_turn = true;
+        }
+
+        if let Some(msg) = net.try_recv_result() {
+            match msg {
+                net::ServerMessage::StrikeResult { pos, result, sunk } => {
+                    self.opponent_view[pos.0][pos.1] = result;
+                    self.game.message = match (&result, &sunk) {
+                        (Shot::Hit, Some(name)) => format!("You sunk their {name}!"),
+                        (Shot::Hit, None) => format!("Hit at ({}, {})!", pos.0 + 1, pos.1 + 1),
+                        _ => format!("Miss at ({}, {})!", pos.0 + 1, pos.1 + 1),
+                    };
+                    self.is_my_turn = false;
+                }
+                net::ServerMessage::WonGame { .. } => {
+                    self.game.message = "You sunk their whole fleet — you win!".to_owned();
+                    self.game.game_over = true;
+                }
+                net::ServerMessage::LostGame { .. } => {
+                    // Nothing in this engine sends LostGame; a peer that does
+                    // ends the match as our loss.
+                    self.game.message = "The match ended — you lose!".to_owned();
+                    self.game.game_over = true;
+                }
+            }
         }
     }
+
+    /// Renders the manual placement phase: a rotate toggle, a Randomize
+    /// shortcut, and a board where clicking an empty cell places the ship
+    /// currently up (`next_ship` indexes `game.ships_config`) via
+    /// `try_place_ship`.
+    fn show_placement_ui(&mut self, ui: &mut egui::Ui, next_ship: usize, horizontal: bool) {
+        let (name, length) = self.game.ships_config[next_ship].clone();
+        ui.label(format!("Place your {name} ({length} cells)"));
+        ui.horizontal(|ui| {
+            if ui
+                .button(if horizontal { "Horizontal" } else { "Vertical" })
+                .clicked()
+            {
+                self.mode = AppMode::Placing { next_ship, horizontal: !horizontal };
+            }
+            if ui.button("Randomize").clicked() {
+                match self.game.start_game() {
+                    Ok(()) => self.mode = AppMode::Playing,
+                    Err(err) => self.game.message = err,
+                }
+            }
+        });
+
+        egui::Grid::new("placement_grid").spacing([8.0, 8.0]).show(ui, |ui| {
+            ui.label("");
+            for col in 0..self.game.cols {
+                ui.label(format!("{}", col + 1));
+            }
+            ui.end_row();
+            for row in 0..self.game.rows {
+                ui.label(format!("{}", row + 1));
+                for col in 0..self.game.cols {
+                    let occupied = self.game.board[row][col] != Cell::Empty;
+                    let color = if occupied {
+                        egui::Color32::DARK_GREEN
+                    } else {
+                        egui::Color32::GRAY
+                    };
+                    let button = egui::Button::new(if occupied { "#" } else { " " }).fill(color);
+                    if ui.add(button).clicked() && !occupied {
+                        match self.game.try_place_ship(next_ship, row, col, horizontal) {
+                            Ok(()) => {
+                                let done = next_ship + 1 >= self.game.ships_config.len();
+                                self.mode = if done {
+                                    AppMode::Playing
+                                } else {
+                                    AppMode::Placing { next_ship: next_ship + 1, horizontal }
+                                };
+                                self.game.message = if done {
+                                    "Fleet placed — take your shot!".to_owned()
+                                } else {
+                                    format!("Placed the {name}.")
+                                };
+                            }
+                            Err(err) => self.game.message = format!("Can't place there: {err}"),
+                        }
+                    }
+                }
+                ui.end_row();
+            }
+        });
+    }
 }
 
 impl eframe::App for BattleshipApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.poll_network();
+
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.heading("Battleship Game");
             ui.label(&self.game.message);
@@ -201,19 +637,93 @@ impl eframe::App for BattleshipApp {
                 ui.label("Columns:");
                 ui.add(egui::DragValue::new(&mut self.input_cols).clamp_range(4..=20));
                 if ui.button("Start New Game").clicked() {
-                    self.game = BattleshipGame::new(self.input_rows, self.input_cols);
-                    self.awaiting_new_game = false;
+                    let fleet = Fleet {
+                        rows: self.input_rows,
+                        cols: self.input_cols,
+                        ships: self.fleet.ships.clone(),
+                    };
+                    match fleet.validate() {
+                        Ok(()) => {
+                            self.game = BattleshipGame::new_unplaced(&fleet);
+                            self.mode = AppMode::Placing { next_ship: 0, horizontal: true };
+                            self.opponent_view =
+                                vec![vec![Shot::Untargeted; fleet.cols]; fleet.rows];
+                            self.fleet = fleet;
+                            self.net = None;
+                            self.net_runtime = None;
+                            self.awaiting_new_game = false;
+                        }
+                        Err(err) => self.game.message = format!("Can't start: {err}"),
+                    }
+                }
+            });
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                ui.label("Address:");
+                ui.text_edit_singleline(&mut self.net_addr);
+                if self.net.is_none() && ui.button("Host").clicked() {
+                    self.start_network_match(net::Role::Host);
+                }
+                if self.net.is_none() && ui.button("Join").clicked() {
+                    self.start_network_match(net::Role::Join);
+                }
+                if ui.button("Save Game").clicked() {
+                    match self.game.save_to(SAVE_FILE) {
+                        Ok(()) => self.game.message = "Game saved.".to_owned(),
+                        Err(err) => self.game.message = format!("Failed to save: {err}"),
+                    }
+                }
+                if ui.button("Load Game").clicked() {
+                    match BattleshipGame::load_from(SAVE_FILE) {
+                        Ok(game) => {
+                            self.opponent_view =
+                                vec![vec![Shot::Untargeted; game.cols]; game.rows];
+                            self.game = game;
+                            self.mode = AppMode::Playing;
+                            self.game.message = "Game loaded.".to_owned();
+                        }
+                        Err(err) => self.game.message = format!("Failed to load: {err}"),
+                    }
                 }
             });
             ui.separator();
 
             // Board size controls and Start New Game button
             ui.separator();
+
+            let mode = match &self.mode {
+                AppMode::Placing { next_ship, horizontal } => Some((*next_ship, *horizontal)),
+                AppMode::Playing => None,
+            };
+            if let Some((next_ship, horizontal)) = mode {
+                self.show_placement_ui(ui, next_ship, horizontal);
+                return;
+            }
+
             // Show game stats
             let stats = self.game.game_stats();
             ui.label(format!("Turns: {} | Hits: {} | Misses: {} | Ships left: {}/{}", stats.turns, stats.hits, stats.misses, stats.ships_left, stats.total_ships));
             ui.separator();
-            // Board display
+            if let Some(net) = &self.net {
+                ui.label(if !net.is_connected() {
+                    "Connecting..."
+                } else if self.is_my_turn {
+                    "Your turn: fire on the opponent's board below."
+                } else {
+                    "Opponent's turn: defending on your board below."
+                });
+                ui.separator();
+                ui.label("Opponent's board");
+            }
+
+            // Board display: against the opponent when playing a networked
+            // match, otherwise the usual solitaire board.
+            let shots = if self.net.is_some() {
+                &self.opponent_view
+            } else {
+                &self.game.shots
+            };
             egui::Grid::new("board_grid").spacing([8.0, 8.0]).show(ui, |ui| {
                 ui.label("");
                 for col in 0..self.game.cols {
@@ -223,12 +733,12 @@ impl eframe::App for BattleshipApp {
                 for row in 0..self.game.rows {
                     ui.label(format!("{}", row + 1));
                     for col in 0..self.game.cols {
-                        let ch = match self.game.shots[row][col] {
+                        let ch = match shots[row][col] {
                             Shot::Untargeted => " ",
                             Shot::Miss => "O",
                             Shot::Hit => "X",
                         };
-                        let color = match self.game.shots[row][col] {
+                        let color = match shots[row][col] {
                             Shot::Hit => egui::Color32::RED,
                             Shot::Miss => egui::Color32::LIGHT_BLUE,
                             Shot::Untargeted => egui::Color32::GRAY,
@@ -237,13 +747,49 @@ impl eframe::App for BattleshipApp {
                         if ui.add(button).clicked() && !self.game.game_over {
                             self.selected_row = row;
                             self.selected_col = col;
-                            self.game.shoot(row, col);
+                            match &self.net {
+                                Some(net) if self.is_my_turn && net.is_connected() => {
+                                    net.send_strike(row, col);
+                                    self.is_my_turn = false;
+                                }
+                                Some(_) => {}
+                                None => self.game.shoot(row, col),
+                            }
                         }
                     }
                     ui.end_row();
                 }
             });
 
+            if self.net.is_some() {
+                ui.separator();
+                ui.label("Your fleet");
+                egui::Grid::new("own_board_grid").spacing([8.0, 8.0]).show(ui, |ui| {
+                    ui.label("");
+                    for col in 0..self.game.cols {
+                        ui.label(format!("{}", col + 1));
+                    }
+                    ui.end_row();
+                    for row in 0..self.game.rows {
+                        ui.label(format!("{}", row + 1));
+                        for col in 0..self.game.cols {
+                            let ch = match self.game.shots[row][col] {
+                                Shot::Untargeted => " ",
+                                Shot::Miss => "O",
+                                Shot::Hit => "X",
+                            };
+                            let color = match self.game.shots[row][col] {
+                                Shot::Hit => egui::Color32::RED,
+                                Shot::Miss => egui::Color32::LIGHT_BLUE,
+                                Shot::Untargeted => egui::Color32::GRAY,
+                            };
+                            ui.add_enabled(false, egui::Button::new(ch).fill(color));
+                        }
+                        ui.end_row();
+                    }
+                });
+            }
+
             ui.separator();
             if self.game.game_over {
                 if ui.button("Play Again").clicked() {
@@ -255,6 +801,14 @@ impl eframe::App for BattleshipApp {
 }
 
 fn main() {
+    if std::env::args().any(|arg| arg == "--headless") {
+        if let Err(err) = headless::run() {
+            eprintln!("headless mode failed: {err}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
     let options = eframe::NativeOptions::default();
     eframe::run_native(
         "Battleship Game",
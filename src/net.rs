@@ -0,0 +1,215 @@
+//! Networking subsystem for two-player matches played over a WebSocket
+//! connection, with one side firing and the other defending each turn.
+
+use std::future::Future;
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use futures_util::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{accept_async, connect_async, MaybeTlsStream, WebSocketStream};
+
+use crate::{Cell, Shot};
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// Sent by the firing player to request a shot on the opponent's board.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ClientMessage {
+    /// Fire at `(row, col)` on the opponent's board.
+    Strike { row: usize, col: usize },
+}
+
+/// Sent by the defending player once a `Strike` has been resolved locally.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ServerMessage {
+    /// Outcome of a single strike, including the sunk ship's name if any.
+    StrikeResult {
+        pos: (usize, usize),
+        result: Shot,
+        sunk: Option<String>,
+    },
+    /// The firing player sank every ship; carries the loser's final board.
+    WonGame { board: Vec<Vec<Cell>> },
+    /// Tells the receiver it lost the match; carries the final board. A wiped
+    /// out defender sends `WonGame` instead, and nothing here sends this.
+    LostGame { board: Vec<Vec<Cell>> },
+}
+
+/// Which side of a networked match this instance is playing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    /// Opened the listening socket and waits for a `Join`.
+    Host,
+    /// Connected to a `Host`'s listener.
+    Join,
+}
+
+/// A message as it travels over the wire, tagging which of the two
+/// directions it belongs to so a single socket can carry both.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum WireMessage {
+    Client(ClientMessage),
+    Server(ServerMessage),
+}
+
+/// A live networked match: a channel pair that shuttles messages to and from
+/// the opponent so `BattleshipApp` can poll it from `update` without
+/// blocking the UI thread. Each side alternates between firing (sending a
+/// `ClientMessage::Strike`) and defending (sending back a `ServerMessage`).
+pub struct NetSession {
+    pub role: Role,
+    outgoing: mpsc::UnboundedSender<WireMessage>,
+    incoming: mpsc::UnboundedReceiver<WireMessage>,
+    /// Holds a message pulled out of `incoming` that didn't match the
+    /// direction the caller asked for, until the matching call comes in.
+    pending: Option<WireMessage>,
+    connected: Arc<AtomicBool>,
+    connect_error: Arc<Mutex<Option<String>>>,
+}
+
+impl NetSession {
+    /// Starts binding `addr` and waiting for one opponent to connect in the
+    /// background; returns immediately so the UI thread never blocks on the
+    /// handshake. Poll [`Self::is_connected`] / [`Self::take_connect_error`].
+    pub fn host(addr: &str, runtime: &tokio::runtime::Handle) -> Self {
+        let addr = addr.to_owned();
+        Self::spawn_connecting(runtime, Role::Host, async move {
+            let listener = TcpListener::bind(&addr).await.map_err(to_io_error)?;
+            let (stream, _) = listener.accept().await.map_err(to_io_error)?;
+            accept_async(MaybeTlsStream::Plain(stream))
+                .await
+                .map_err(to_io_error)
+        })
+    }
+
+    /// Starts connecting to a hosting opponent at `addr` in the background;
+    /// returns immediately so the UI thread never blocks on the handshake.
+    pub fn join(addr: &str, runtime: &tokio::runtime::Handle) -> Self {
+        let url = format!("ws://{addr}");
+        Self::spawn_connecting(runtime, Role::Join, async move {
+            let (socket, _) = connect_async(url).await.map_err(to_io_error)?;
+            Ok(socket)
+        })
+    }
+
+    /// Spawns `connect` on `runtime`, then hands its socket off to `relay`
+    /// once the handshake completes. Connection progress is surfaced
+    /// through `connected`/`connect_error` instead of blocking the caller.
+    fn spawn_connecting(
+        runtime: &tokio::runtime::Handle,
+        role: Role,
+        connect: impl Future<Output = io::Result<Socket>> + Send + 'static,
+    ) -> Self {
+        let (to_remote_tx, to_remote_rx) = mpsc::unbounded_channel();
+        let (from_remote_tx, from_remote_rx) = mpsc::unbounded_channel();
+        let connected = Arc::new(AtomicBool::new(false));
+        let connect_error = Arc::new(Mutex::new(None));
+
+        let connected_handle = connected.clone();
+        let error_handle = connect_error.clone();
+        runtime.spawn(async move {
+            match connect.await {
+                Ok(socket) => {
+                    connected_handle.store(true, Ordering::SeqCst);
+                    relay(socket, to_remote_rx, from_remote_tx).await;
+                }
+                Err(err) => *error_handle.lock().unwrap() = Some(err.to_string()),
+            }
+        });
+
+        Self {
+            role,
+            outgoing: to_remote_tx,
+            incoming: from_remote_rx,
+            pending: None,
+            connected,
+            connect_error,
+        }
+    }
+
+    /// Whether the handshake has completed and the match can begin.
+    pub fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::SeqCst)
+    }
+
+    /// Takes the handshake failure, if the connect/accept attempt failed.
+    pub fn take_connect_error(&self) -> Option<String> {
+        self.connect_error.lock().unwrap().take()
+    }
+
+    /// Queues a `Strike` to be sent to the opponent.
+    pub fn send_strike(&self, row: usize, col: usize) {
+        let _ = self
+            .outgoing
+            .send(WireMessage::Client(ClientMessage::Strike { row, col }));
+    }
+
+    /// Queues the result of a strike the opponent just made against us.
+    pub fn send_result(&self, msg: ServerMessage) {
+        let _ = self.outgoing.send(WireMessage::Server(msg));
+    }
+
+    /// Pulls the next wire message, preferring one stashed by the other
+    /// `try_recv_*` call over reading a fresh one off the channel.
+    fn try_recv_wire(&mut self) -> Option<WireMessage> {
+        self.pending.take().or_else(|| self.incoming.try_recv().ok())
+    }
+
+    /// Drains the next incoming `Strike` request from the opponent, if any.
+    pub fn try_recv_strike(&mut self) -> Option<ClientMessage> {
+        match self.try_recv_wire()? {
+            WireMessage::Client(msg) => Some(msg),
+            other => {
+                // Not our turn to defend yet; put it back for try_recv_result.
+                self.pending = Some(other);
+                None
+            }
+        }
+    }
+
+    /// Drains the next incoming strike result from the opponent, if any.
+    pub fn try_recv_result(&mut self) -> Option<ServerMessage> {
+        match self.try_recv_wire()? {
+            WireMessage::Server(msg) => Some(msg),
+            other => {
+                self.pending = Some(other);
+                None
+            }
+        }
+    }
+}
+
+async fn relay(
+    mut socket: Socket,
+    mut outgoing: mpsc::UnboundedReceiver<WireMessage>,
+    incoming: mpsc::UnboundedSender<WireMessage>,
+) {
+    loop {
+        tokio::select! {
+            Some(msg) = outgoing.recv() => {
+                let Ok(text) = serde_json::to_string(&msg) else { continue };
+                if socket.send(Message::Text(text)).await.is_err() {
+                    return;
+                }
+            }
+            Some(frame) = socket.next() => {
+                let Ok(frame) = frame else { return };
+                let Ok(text) = frame.into_text() else { continue };
+                let Ok(msg) = serde_json::from_str::<WireMessage>(&text) else { continue };
+                if incoming.send(msg).is_err() {
+                    return;
+                }
+            }
+            else => return,
+        }
+    }
+}
+
+fn to_io_error<E: std::fmt::Display>(err: E) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, err.to_string())
+}